@@ -1,3 +1,4 @@
+use num_traits::{Float, Num};
 use std::ops::{
     Add,
     AddAssign,
@@ -6,56 +7,84 @@ use std::ops::{
     Mul,
     MulAssign,
     Div,
-    DivAssign
+    DivAssign,
+    Neg,
+    Index,
+    IndexMut
 };
 
 //===============================================
 // Vec3 class
+//
+// Generic over its scalar type so it can back integer grid coordinates,
+// f32 GPU-bound data, or f64 precision work. `Vec3` used without a type
+// argument defaults to `Vec3<f64>`, so existing call sites are unaffected.
 
 #[derive(PartialEq, Debug)]
-pub struct Vec3 {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
+pub struct Vec3<T = f64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
 }
 
-impl Vec3 {
+impl<T: Num + Copy> Vec3<T> {
     //===============================================
-    pub fn length_squared(&self) -> f64 {
-        return
-            self.x * self.x +
-            self.y * self.y +
-            self.z * self.z;
+    pub fn cross(&self, other: &Vec3<T>) -> Vec3<T> {
+        Vec3 {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x
+        }
     }
 
     //===============================================
-    pub fn normalize(&self) -> Vec3 {
-        return self / self.length_squared().sqrt();
+    pub fn dot(&self, other: &Vec3<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
     }
 
     //===============================================
-    pub fn cross(&self, other: &Vec3) -> Vec3 {
+    pub fn length_squared(&self) -> T {
+        self.dot(self)
+    }
+}
+
+impl<T: Float> Vec3<T> {
+    //===============================================
+    // Component-wise fused multiply-add: self * a + b, computed with a
+    // single rounding error per term via the hardware FMA instruction.
+    pub fn mul_add(self, a: T, b: &Vec3<T>) -> Vec3<T> {
         Vec3 {
-            x: self.y * other.z - self.z * other.y,
-            y: self.z * other.x - self.x * other.z,
-            z: self.x * other.y - self.y * other.x
+            x: self.x.mul_add(a, b.x),
+            y: self.y.mul_add(a, b.y),
+            z: self.z.mul_add(a, b.z),
         }
     }
 
     //===============================================
-    pub fn dot(&self, other: &Vec3) -> f64 {
-        return
-            self.x * other.x +
-            self.y * other.y +
-            self.z * other.z;
+    // FMA-based `dot`/`length_squared`, for accumulation-heavy kernels
+    // (e.g. `reflect`/`refract`/`Sphere::hit`) where one rounding error
+    // per term is worth the extra method name. `dot`/`length_squared`
+    // stay plain-arithmetic and generic over any `Num + Copy` scalar.
+    pub fn dot_fma(&self, other: &Vec3<T>) -> T {
+        self.x.mul_add(other.x, self.y.mul_add(other.y, self.z * other.z))
+    }
+
+    //===============================================
+    pub fn length_squared_fma(&self) -> T {
+        self.dot_fma(self)
+    }
+
+    //===============================================
+    pub fn normalize(&self) -> Vec3<T> {
+        self / self.length_squared().sqrt()
     }
 }
 
 //===============================================
-impl<'a, 'b> Add<&'b Vec3> for &'a Vec3 {
-    type Output = Vec3;
+impl<'b, T: Num + Copy> Add<&'b Vec3<T>> for &Vec3<T> {
+    type Output = Vec3<T>;
 
-    fn add(self, other: &'b Vec3) -> Vec3 {
+    fn add(self, other: &'b Vec3<T>) -> Vec3<T> {
         Vec3 {
             x: self.x + other.x,
             y: self.y + other.y,
@@ -65,20 +94,20 @@ impl<'a, 'b> Add<&'b Vec3> for &'a Vec3 {
 }
 
 //===============================================
-impl<'b> AddAssign<&'b Vec3> for Vec3 {
-    fn add_assign(&mut self, other: &'b Vec3) {
-        self.x += other.x;
-        self.y += other.y;
-        self.z += other.z;
+impl<'b, T: Num + Copy> AddAssign<&'b Vec3<T>> for Vec3<T> {
+    fn add_assign(&mut self, other: &'b Vec3<T>) {
+        self.x = self.x + other.x;
+        self.y = self.y + other.y;
+        self.z = self.z + other.z;
     }
 }
 
 
 //===============================================
-impl<'a, 'b> Sub<&'b Vec3> for &'a Vec3 {
-    type Output = Vec3;
+impl<'b, T: Num + Copy> Sub<&'b Vec3<T>> for &Vec3<T> {
+    type Output = Vec3<T>;
 
-    fn sub(self, other: &'b Vec3) -> Vec3 {
+    fn sub(self, other: &'b Vec3<T>) -> Vec3<T> {
         Vec3 {
             x: self.x - other.x,
             y: self.y - other.y,
@@ -88,19 +117,19 @@ impl<'a, 'b> Sub<&'b Vec3> for &'a Vec3 {
 }
 
 //===============================================
-impl<'b> SubAssign<&'b Vec3> for Vec3 {
-    fn sub_assign(&mut self, other: &'b Vec3) {
-        self.x -= other.x;
-        self.y -= other.y;
-        self.z -= other.z;
+impl<'b, T: Num + Copy> SubAssign<&'b Vec3<T>> for Vec3<T> {
+    fn sub_assign(&mut self, other: &'b Vec3<T>) {
+        self.x = self.x - other.x;
+        self.y = self.y - other.y;
+        self.z = self.z - other.z;
     }
 }
 
 //===============================================
-impl<'a> Mul<f64> for &'a Vec3 {
-    type Output = Vec3;
+impl<T: Num + Copy> Mul<T> for &Vec3<T> {
+    type Output = Vec3<T>;
 
-    fn mul(self, scaler: f64) -> Vec3 {
+    fn mul(self, scaler: T) -> Vec3<T> {
         Vec3 {
             x: self.x * scaler,
             y: self.y * scaler,
@@ -123,34 +152,484 @@ impl<'a> Mul<&'a Vec3> for f64 {
 }
 
 //===============================================
-impl MulAssign<f64> for Vec3 {
-    fn mul_assign(&mut self, scaler: f64) {
-        self.x *= scaler;
-        self.y *= scaler;
-        self.z *= scaler;
+impl<T: Num + Copy> MulAssign<T> for Vec3<T> {
+    fn mul_assign(&mut self, scaler: T) {
+        self.x = self.x * scaler;
+        self.y = self.y * scaler;
+        self.z = self.z * scaler;
     }
 }
 
 //===============================================
-impl<'a> Div<f64> for &'a Vec3 {
-    type Output = Vec3;
-    fn div(self, divisor: f64) -> Vec3 {
-        let d = 1f64 / divisor;
+impl<T: Num + Copy> Div<T> for &Vec3<T> {
+    type Output = Vec3<T>;
+    fn div(self, divisor: T) -> Vec3<T> {
         Vec3 {
-            x : self.x * d,
-            y : self.y * d,
-            z : self.z * d
+            x : self.x / divisor,
+            y : self.y / divisor,
+            z : self.z / divisor
         }
     }
 }
 
 //===============================================
-impl DivAssign<f64> for Vec3 {
-    fn div_assign(&mut self, divisor: f64) {
-        let d = 1f64 / divisor;
-        self.x *= d;
-        self.y *= d;
-        self.z *= d;
+impl<T: Num + Copy> DivAssign<T> for Vec3<T> {
+    fn div_assign(&mut self, divisor: T) {
+        self.x = self.x / divisor;
+        self.y = self.y / divisor;
+        self.z = self.z / divisor;
+    }
+}
+
+//===============================================
+impl<T: Neg<Output = T> + Copy> Neg for &Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn neg(self) -> Vec3<T> {
+        Vec3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z
+        }
+    }
+}
+
+//===============================================
+// Indexes the axes 0 -> x, 1 -> y, 2 -> z. Panics on any other index.
+impl<T> Index<usize> for Vec3<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Vec3 index out of range: {}", index),
+        }
+    }
+}
+
+//===============================================
+impl<T> IndexMut<usize> for Vec3<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Vec3 index out of range: {}", index),
+        }
+    }
+}
+
+impl<T: Num + Copy> Vec3<T> {
+    //===============================================
+    pub fn lerp(&self, other: &Vec3<T>, t: T) -> Vec3<T> {
+        self + &(&(other - self) * t)
+    }
+}
+
+impl<T: PartialOrd + Copy> Vec3<T> {
+    //===============================================
+    pub fn min(&self, other: &Vec3<T>) -> Vec3<T> {
+        Vec3 {
+            x: if self.x < other.x { self.x } else { other.x },
+            y: if self.y < other.y { self.y } else { other.y },
+            z: if self.z < other.z { self.z } else { other.z },
+        }
+    }
+
+    //===============================================
+    pub fn max(&self, other: &Vec3<T>) -> Vec3<T> {
+        Vec3 {
+            x: if self.x > other.x { self.x } else { other.x },
+            y: if self.y > other.y { self.y } else { other.y },
+            z: if self.z > other.z { self.z } else { other.z },
+        }
+    }
+}
+
+impl Vec3 {
+    //===============================================
+    pub fn reflect(&self, normal: &Vec3) -> Vec3 {
+        let scaled = (2f64 * self.dot_fma(normal)) * normal;
+        self - &scaled
+    }
+
+    //===============================================
+    // Snell's law refraction. Returns `None` on total internal reflection.
+    pub fn refract(&self, normal: &Vec3, eta_ratio: f64) -> Option<Vec3> {
+        let cos_theta = (-self.dot_fma(normal)).min(1f64);
+        let r_out_perp = eta_ratio * &(self + &(cos_theta * normal));
+        if r_out_perp.length_squared_fma() > 1f64 {
+            return None;
+        }
+
+        let r_out_parallel = (-(1f64 - r_out_perp.length_squared_fma()).sqrt()) * normal;
+        Some(&r_out_perp + &r_out_parallel)
+    }
+}
+
+//===============================================
+// Quat class
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Quat {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+}
+
+impl Quat {
+    //===============================================
+    pub fn from_axis_angle(axis: &Vec3, angle: f64) -> Quat {
+        let n = axis.normalize();
+        let half = angle * 0.5;
+        let s = half.sin();
+        Quat {
+            a: half.cos(),
+            b: n.x * s,
+            c: n.y * s,
+            d: n.z * s,
+        }
+    }
+
+    //===============================================
+    pub fn conjugate(&self) -> Quat {
+        Quat {
+            a: self.a,
+            b: -self.b,
+            c: -self.c,
+            d: -self.d,
+        }
+    }
+
+    //===============================================
+    pub fn rotate(&self, v: &Vec3) -> Vec3 {
+        let qv = Quat { a: 0f64, b: v.x, c: v.y, d: v.z };
+        let rotated = &(self * &qv) * &self.conjugate();
+        Vec3 { x: rotated.b, y: rotated.c, z: rotated.d }
+    }
+}
+
+//===============================================
+impl<'b> Mul<&'b Quat> for &Quat {
+    type Output = Quat;
+
+    fn mul(self, other: &'b Quat) -> Quat {
+        Quat {
+            a: self.a * other.a - self.b * other.b - self.c * other.c - self.d * other.d,
+            b: self.a * other.b + self.b * other.a + self.c * other.d - self.d * other.c,
+            c: self.a * other.c - self.b * other.d + self.c * other.a + self.d * other.b,
+            d: self.a * other.d + self.b * other.c - self.c * other.b + self.d * other.a,
+        }
+    }
+}
+
+//===============================================
+// Transform class
+
+#[derive(PartialEq, Debug)]
+pub struct Transform {
+    pub orientation: Quat,
+    pub position: Vec3,
+}
+
+impl Transform {
+    //===============================================
+    // Bakes the orientation and position down to a column-major 4x4 matrix
+    // suitable for handing off to a renderer.
+    pub fn to_mat4(&self) -> [f64; 16] {
+        let Quat { a, b, c, d } = self.orientation;
+
+        let row0 = (1f64 - 2f64 * c * c - 2f64 * d * d, 2f64 * a * b - 2f64 * c * d, 2f64 * a * c + 2f64 * b * d);
+        let row1 = (2f64 * a * b + 2f64 * c * d, 1f64 - 2f64 * b * b - 2f64 * d * d, 2f64 * b * c - 2f64 * a * d);
+        let row2 = (2f64 * a * c - 2f64 * b * d, 2f64 * b * c + 2f64 * a * d, 1f64 - 2f64 * b * b - 2f64 * c * c);
+
+        [
+            row0.0, row1.0, row2.0, 0f64,
+            row0.1, row1.1, row2.1, 0f64,
+            row0.2, row1.2, row2.2, 0f64,
+            self.position.x, self.position.y, self.position.z, 1f64,
+        ]
+    }
+}
+
+//===============================================
+// Mat3 class
+//
+// Stored column-major: data[col * 3 + row].
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Mat3 {
+    pub data: [f64; 9],
+}
+
+impl Mat3 {
+    //===============================================
+    pub fn identity() -> Mat3 {
+        Mat3 {
+            data: [
+                1f64, 0f64, 0f64,
+                0f64, 1f64, 0f64,
+                0f64, 0f64, 1f64,
+            ]
+        }
+    }
+
+    //===============================================
+    pub fn transpose(&self) -> Mat3 {
+        let m = &self.data;
+        Mat3 {
+            data: [
+                m[0], m[3], m[6],
+                m[1], m[4], m[7],
+                m[2], m[5], m[8],
+            ]
+        }
+    }
+
+    //===============================================
+    // Inverse via the cross-product adjugate: the rows of the inverse are
+    // the cross products of the columns, scaled by 1/det.
+    pub fn inverse(&self) -> Mat3 {
+        let m = &self.data;
+        let c0 = Vec3 {x: m[0], y: m[1], z: m[2]};
+        let c1 = Vec3 {x: m[3], y: m[4], z: m[5]};
+        let c2 = Vec3 {x: m[6], y: m[7], z: m[8]};
+
+        let r0 = c1.cross(&c2);
+        let r1 = c2.cross(&c0);
+        let r2 = c0.cross(&c1);
+
+        let det = c0.dot(&r0);
+        let inv_det = 1f64 / det;
+
+        Mat3 {
+            data: [
+                r0.x * inv_det, r1.x * inv_det, r2.x * inv_det,
+                r0.y * inv_det, r1.y * inv_det, r2.y * inv_det,
+                r0.z * inv_det, r1.z * inv_det, r2.z * inv_det,
+            ]
+        }
+    }
+
+    //===============================================
+    pub fn mul_vec3(&self, v: &Vec3) -> Vec3 {
+        let m = &self.data;
+        Vec3 {
+            x: m[0] * v.x + m[3] * v.y + m[6] * v.z,
+            y: m[1] * v.x + m[4] * v.y + m[7] * v.z,
+            z: m[2] * v.x + m[5] * v.y + m[8] * v.z,
+        }
+    }
+}
+
+//===============================================
+impl<'b> Mul<&'b Mat3> for &Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, other: &'b Mat3) -> Mat3 {
+        let a = &self.data;
+        let b = &other.data;
+        let mut data = [0f64; 9];
+        for col in 0..3 {
+            for row in 0..3 {
+                let mut sum = 0f64;
+                for k in 0..3 {
+                    sum += a[k * 3 + row] * b[col * 3 + k];
+                }
+                data[col * 3 + row] = sum;
+            }
+        }
+        Mat3 { data }
+    }
+}
+
+//===============================================
+// Mat4 class
+//
+// Stored column-major: data[col * 4 + row].
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Mat4 {
+    pub data: [f64; 16],
+}
+
+impl Mat4 {
+    //===============================================
+    pub fn identity() -> Mat4 {
+        Mat4 {
+            data: [
+                1f64, 0f64, 0f64, 0f64,
+                0f64, 1f64, 0f64, 0f64,
+                0f64, 0f64, 1f64, 0f64,
+                0f64, 0f64, 0f64, 1f64,
+            ]
+        }
+    }
+
+    //===============================================
+    pub fn translation(v: &Vec3) -> Mat4 {
+        let mut m = Mat4::identity();
+        m.data[12] = v.x;
+        m.data[13] = v.y;
+        m.data[14] = v.z;
+        m
+    }
+
+    //===============================================
+    pub fn scale(v: &Vec3) -> Mat4 {
+        Mat4 {
+            data: [
+                v.x, 0f64, 0f64, 0f64,
+                0f64, v.y, 0f64, 0f64,
+                0f64, 0f64, v.z, 0f64,
+                0f64, 0f64, 0f64, 1f64,
+            ]
+        }
+    }
+
+    //===============================================
+    pub fn rotation_x(angle: f64) -> Mat4 {
+        let (s, c) = angle.sin_cos();
+        Mat4 {
+            data: [
+                1f64, 0f64, 0f64, 0f64,
+                0f64, c, s, 0f64,
+                0f64, -s, c, 0f64,
+                0f64, 0f64, 0f64, 1f64,
+            ]
+        }
+    }
+
+    //===============================================
+    pub fn rotation_y(angle: f64) -> Mat4 {
+        let (s, c) = angle.sin_cos();
+        Mat4 {
+            data: [
+                c, 0f64, -s, 0f64,
+                0f64, 1f64, 0f64, 0f64,
+                s, 0f64, c, 0f64,
+                0f64, 0f64, 0f64, 1f64,
+            ]
+        }
+    }
+
+    //===============================================
+    pub fn rotation_z(angle: f64) -> Mat4 {
+        let (s, c) = angle.sin_cos();
+        Mat4 {
+            data: [
+                c, s, 0f64, 0f64,
+                -s, c, 0f64, 0f64,
+                0f64, 0f64, 1f64, 0f64,
+                0f64, 0f64, 0f64, 1f64,
+            ]
+        }
+    }
+
+    //===============================================
+    // Right-handed view matrix looking from `eye` toward `target`.
+    pub fn look_at(eye: &Vec3, target: &Vec3, up: &Vec3) -> Mat4 {
+        let z_axis = (eye - target).normalize();
+        let x_axis = up.cross(&z_axis).normalize();
+        let y_axis = z_axis.cross(&x_axis);
+
+        Mat4 {
+            data: [
+                x_axis.x, y_axis.x, z_axis.x, 0f64,
+                x_axis.y, y_axis.y, z_axis.y, 0f64,
+                x_axis.z, y_axis.z, z_axis.z, 0f64,
+                -x_axis.dot(eye), -y_axis.dot(eye), -z_axis.dot(eye), 1f64,
+            ]
+        }
+    }
+
+    //===============================================
+    pub fn transpose(&self) -> Mat4 {
+        let m = &self.data;
+        Mat4 {
+            data: [
+                m[0], m[4], m[8], m[12],
+                m[1], m[5], m[9], m[13],
+                m[2], m[6], m[10], m[14],
+                m[3], m[7], m[11], m[15],
+            ]
+        }
+    }
+
+    //===============================================
+    // Cofactor-expansion inverse of a general 4x4 matrix.
+    pub fn inverse(&self) -> Mat4 {
+        let m = &self.data;
+        let mut inv = [0f64; 16];
+
+        inv[0] = m[5] * m[10] * m[15] - m[5] * m[11] * m[14] - m[9] * m[6] * m[15] + m[9] * m[7] * m[14] + m[13] * m[6] * m[11] - m[13] * m[7] * m[10];
+        inv[4] = -m[4] * m[10] * m[15] + m[4] * m[11] * m[14] + m[8] * m[6] * m[15] - m[8] * m[7] * m[14] - m[12] * m[6] * m[11] + m[12] * m[7] * m[10];
+        inv[8] = m[4] * m[9] * m[15] - m[4] * m[11] * m[13] - m[8] * m[5] * m[15] + m[8] * m[7] * m[13] + m[12] * m[5] * m[11] - m[12] * m[7] * m[9];
+        inv[12] = -m[4] * m[9] * m[14] + m[4] * m[10] * m[13] + m[8] * m[5] * m[14] - m[8] * m[6] * m[13] - m[12] * m[5] * m[10] + m[12] * m[6] * m[9];
+        inv[1] = -m[1] * m[10] * m[15] + m[1] * m[11] * m[14] + m[9] * m[2] * m[15] - m[9] * m[3] * m[14] - m[13] * m[2] * m[11] + m[13] * m[3] * m[10];
+        inv[5] = m[0] * m[10] * m[15] - m[0] * m[11] * m[14] - m[8] * m[2] * m[15] + m[8] * m[3] * m[14] + m[12] * m[2] * m[11] - m[12] * m[3] * m[10];
+        inv[9] = -m[0] * m[9] * m[15] + m[0] * m[11] * m[13] + m[8] * m[1] * m[15] - m[8] * m[3] * m[13] - m[12] * m[1] * m[11] + m[12] * m[3] * m[9];
+        inv[13] = m[0] * m[9] * m[14] - m[0] * m[10] * m[13] - m[8] * m[1] * m[14] + m[8] * m[2] * m[13] + m[12] * m[1] * m[10] - m[12] * m[2] * m[9];
+        inv[2] = m[1] * m[6] * m[15] - m[1] * m[7] * m[14] - m[5] * m[2] * m[15] + m[5] * m[3] * m[14] + m[13] * m[2] * m[7] - m[13] * m[3] * m[6];
+        inv[6] = -m[0] * m[6] * m[15] + m[0] * m[7] * m[14] + m[4] * m[2] * m[15] - m[4] * m[3] * m[14] - m[12] * m[2] * m[7] + m[12] * m[3] * m[6];
+        inv[10] = m[0] * m[5] * m[15] - m[0] * m[7] * m[13] - m[4] * m[1] * m[15] + m[4] * m[3] * m[13] + m[12] * m[1] * m[7] - m[12] * m[3] * m[5];
+        inv[14] = -m[0] * m[5] * m[14] + m[0] * m[6] * m[13] + m[4] * m[1] * m[14] - m[4] * m[2] * m[13] - m[12] * m[1] * m[6] + m[12] * m[2] * m[5];
+        inv[3] = -m[1] * m[6] * m[11] + m[1] * m[7] * m[10] + m[5] * m[2] * m[11] - m[5] * m[3] * m[10] - m[9] * m[2] * m[7] + m[9] * m[3] * m[6];
+        inv[7] = m[0] * m[6] * m[11] - m[0] * m[7] * m[10] - m[4] * m[2] * m[11] + m[4] * m[3] * m[10] + m[8] * m[2] * m[7] - m[8] * m[3] * m[6];
+        inv[11] = -m[0] * m[5] * m[11] + m[0] * m[7] * m[9] + m[4] * m[1] * m[11] - m[4] * m[3] * m[9] - m[8] * m[1] * m[7] + m[8] * m[3] * m[5];
+        inv[15] = m[0] * m[5] * m[10] - m[0] * m[6] * m[9] - m[4] * m[1] * m[10] + m[4] * m[2] * m[9] + m[8] * m[1] * m[6] - m[8] * m[2] * m[5];
+
+        let det = m[0] * inv[0] + m[1] * inv[4] + m[2] * inv[8] + m[3] * inv[12];
+        let inv_det = 1f64 / det;
+        for v in inv.iter_mut() {
+            *v *= inv_det;
+        }
+
+        Mat4 { data: inv }
+    }
+
+    //===============================================
+    pub fn transform_point(&self, v: &Vec3) -> Vec3 {
+        let m = &self.data;
+        Vec3 {
+            x: m[0] * v.x + m[4] * v.y + m[8] * v.z + m[12],
+            y: m[1] * v.x + m[5] * v.y + m[9] * v.z + m[13],
+            z: m[2] * v.x + m[6] * v.y + m[10] * v.z + m[14],
+        }
+    }
+
+    //===============================================
+    pub fn transform_direction(&self, v: &Vec3) -> Vec3 {
+        let m = &self.data;
+        Vec3 {
+            x: m[0] * v.x + m[4] * v.y + m[8] * v.z,
+            y: m[1] * v.x + m[5] * v.y + m[9] * v.z,
+            z: m[2] * v.x + m[6] * v.y + m[10] * v.z,
+        }
+    }
+}
+
+//===============================================
+impl<'b> Mul<&'b Mat4> for &Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, other: &'b Mat4) -> Mat4 {
+        let a = &self.data;
+        let b = &other.data;
+        let mut data = [0f64; 16];
+        for col in 0..4 {
+            for row in 0..4 {
+                let mut sum = 0f64;
+                for k in 0..4 {
+                    sum += a[k * 4 + row] * b[col * 4 + k];
+                }
+                data[col * 4 + row] = sum;
+            }
+        }
+        Mat4 { data }
     }
 }
 
@@ -165,7 +644,53 @@ pub struct Ray {
 
 impl Ray {
     pub fn point_at(&self, t: f64) -> Vec3 {
-        return &self.origin + &(&self.dir * t);
+        &self.origin + &(&self.dir * t)
+    }
+}
+
+//===============================================
+// Hit record produced when a Ray intersects a surface.
+
+#[derive(PartialEq, Debug)]
+pub struct Hit {
+    pub t: f64,
+    pub point: Vec3,
+    pub normal: Vec3,
+}
+
+//===============================================
+// Sphere class
+
+#[derive(PartialEq, Debug)]
+pub struct Sphere {
+    pub center: Vec3,
+    pub radius: f64,
+}
+
+impl Sphere {
+    //===============================================
+    pub fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<Hit> {
+        let oc = &ray.origin - &self.center;
+        let a = ray.dir.dot_fma(&ray.dir);
+        let half_b = oc.dot_fma(&ray.dir);
+        let c = oc.dot_fma(&oc) - self.radius * self.radius;
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0f64 {
+            return None;
+        }
+        let sqrt_d = discriminant.sqrt();
+
+        let mut root = (-half_b - sqrt_d) / a;
+        if root < t_min || root > t_max {
+            root = (-half_b + sqrt_d) / a;
+            if root < t_min || root > t_max {
+                return None;
+            }
+        }
+
+        let point = ray.point_at(root);
+        let normal = &(&point - &self.center) / self.radius;
+        Some(Hit { t: root, point, normal })
     }
 }
 
@@ -249,6 +774,31 @@ mod vec3 {
         assert_eq!(c, 14f64);
     }
 
+    #[test]
+    fn dot_non_float_scalar() {
+        let a = super::Vec3::<i32> {x: 1, y: 2, z: 3};
+        let b = super::Vec3::<i32> {x: 1, y: 2, z: 3};
+        assert_eq!(a.dot(&b), 14);
+        assert_eq!(a.length_squared(), 14);
+    }
+
+    #[test]
+    fn div_non_float_scalar() {
+        let a = super::Vec3::<i32> {x: 9, y: 12, z: 15};
+        let b = &a / 3;
+        assert_eq!(b.x, 3);
+        assert_eq!(b.y, 4);
+        assert_eq!(b.z, 5);
+    }
+
+    #[test]
+    fn dot_fma_matches_dot() {
+        let a = Vec3 {x: 1f64, y: 2f64, z: 3f64};
+        let b = Vec3 {x: 4f64, y: 5f64, z: 6f64};
+        assert_eq!(a.dot_fma(&b), a.dot(&b));
+        assert_eq!(a.length_squared_fma(), a.length_squared());
+    }
+
     #[test]
     fn div() {
         let a = Vec3 {x: 1f64, y: 2f64, z: 3f64};
@@ -273,6 +823,14 @@ mod vec3 {
         assert_eq!(a.length_squared(), 14f64);
     }
 
+    #[test]
+    fn mul_add() {
+        let a = Vec3 {x: 1f64, y: 2f64, z: 3f64};
+        let b = Vec3 {x: 1f64, y: 1f64, z: 1f64};
+        let c = a.mul_add(2f64, &b);
+        assert_eq!(c, Vec3 {x: 3f64, y: 5f64, z: 7f64});
+    }
+
     #[test]
     fn normalize() {
         let a = Vec3 {x: 1f64, y: 2f64, z: 3f64};
@@ -288,6 +846,216 @@ mod vec3 {
         assert_eq!(b.dot(&c), 0f64);
     }
 
+    #[test]
+    fn reflect() {
+        let v = Vec3 {x: 1f64, y: -1f64, z: 0f64};
+        let normal = Vec3 {x: 0f64, y: 1f64, z: 0f64};
+        let r = v.reflect(&normal);
+        assert_eq!(r, Vec3 {x: 1f64, y: 1f64, z: 0f64});
+    }
+
+    #[test]
+    fn refract_straight_through() {
+        let v = Vec3 {x: 0f64, y: -1f64, z: 0f64};
+        let normal = Vec3 {x: 0f64, y: 1f64, z: 0f64};
+        let r = v.refract(&normal, 1f64).unwrap();
+        assert!((r.x - v.x).abs() < 1e-10);
+        assert!((r.y - v.y).abs() < 1e-10);
+        assert!((r.z - v.z).abs() < 1e-10);
+    }
+
+    #[test]
+    fn refract_total_internal_reflection() {
+        let v = Vec3 {x: 1f64, y: -0.1f64, z: 0f64}.normalize();
+        let normal = Vec3 {x: 0f64, y: 1f64, z: 0f64};
+        assert_eq!(v.refract(&normal, 1.5f64), None);
+    }
+
+    #[test]
+    fn neg() {
+        let a = Vec3 {x: 1f64, y: -2f64, z: 3f64};
+        let b = -&a;
+        assert_eq!(b, Vec3 {x: -1f64, y: 2f64, z: -3f64});
+    }
+
+    #[test]
+    fn index() {
+        let a = Vec3 {x: 1f64, y: 2f64, z: 3f64};
+        assert_eq!(a[0], 1f64);
+        assert_eq!(a[1], 2f64);
+        assert_eq!(a[2], 3f64);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_range() {
+        let a = Vec3 {x: 1f64, y: 2f64, z: 3f64};
+        let _ = a[3];
+    }
+
+    #[test]
+    fn index_mut() {
+        let mut a = Vec3 {x: 1f64, y: 2f64, z: 3f64};
+        a[1] = 5f64;
+        assert_eq!(a.y, 5f64);
+    }
+
+    #[test]
+    fn lerp() {
+        let a = Vec3 {x: 0f64, y: 0f64, z: 0f64};
+        let b = Vec3 {x: 10f64, y: 10f64, z: 10f64};
+        assert_eq!(a.lerp(&b, 0.5f64), Vec3 {x: 5f64, y: 5f64, z: 5f64});
+    }
+
+    #[test]
+    fn min_max() {
+        let a = Vec3 {x: 1f64, y: 5f64, z: 3f64};
+        let b = Vec3 {x: 4f64, y: 2f64, z: 6f64};
+        assert_eq!(a.min(&b), Vec3 {x: 1f64, y: 2f64, z: 3f64});
+        assert_eq!(a.max(&b), Vec3 {x: 4f64, y: 5f64, z: 6f64});
+    }
+
+}
+
+//===============================================
+// Quat Unit Tests
+
+//===============================================
+#[cfg(test)]
+mod quat {
+    use super::Quat;
+    use super::Vec3;
+
+    #[test]
+    fn from_axis_angle_identity() {
+        let axis = Vec3 {x: 0f64, y: 1f64, z: 0f64};
+        let q = Quat::from_axis_angle(&axis, 0f64);
+        assert_eq!(q, Quat {a: 1f64, b: 0f64, c: 0f64, d: 0f64});
+    }
+
+    #[test]
+    fn rotate_around_z() {
+        let axis = Vec3 {x: 0f64, y: 0f64, z: 1f64};
+        let q = Quat::from_axis_angle(&axis, std::f64::consts::PI);
+        let v = Vec3 {x: 1f64, y: 0f64, z: 0f64};
+        let rotated = q.rotate(&v);
+        assert!((rotated.x - (-1f64)).abs() < 1e-10);
+        assert!(rotated.y.abs() < 1e-10);
+        assert!(rotated.z.abs() < 1e-10);
+    }
+
+    #[test]
+    fn hamilton_product_composition() {
+        let axis = Vec3 {x: 0f64, y: 0f64, z: 1f64};
+        let half = Quat::from_axis_angle(&axis, std::f64::consts::PI / 2f64);
+        let composed = &half * &half;
+        let full = Quat::from_axis_angle(&axis, std::f64::consts::PI);
+        assert!((composed.a - full.a).abs() < 1e-10);
+        assert!((composed.d - full.d).abs() < 1e-10);
+    }
+}
+
+//===============================================
+// Transform Unit Tests
+
+//===============================================
+#[cfg(test)]
+mod transform {
+    use super::Quat;
+    use super::Transform;
+    use super::Vec3;
+
+    #[test]
+    fn to_mat4_identity_orientation() {
+        let transform = Transform {
+            orientation: Quat {a: 1f64, b: 0f64, c: 0f64, d: 0f64},
+            position: Vec3 {x: 1f64, y: 2f64, z: 3f64},
+        };
+
+        let m = transform.to_mat4();
+        let expected = [
+            1f64, 0f64, 0f64, 0f64,
+            0f64, 1f64, 0f64, 0f64,
+            0f64, 0f64, 1f64, 0f64,
+            1f64, 2f64, 3f64, 1f64,
+        ];
+        assert_eq!(m, expected);
+    }
+}
+
+//===============================================
+// Mat3/Mat4 Unit Tests
+
+//===============================================
+#[cfg(test)]
+mod mat {
+    use super::Mat3;
+    use super::Mat4;
+    use super::Vec3;
+
+    #[test]
+    fn mat3_identity_mul_vec3() {
+        let m = Mat3::identity();
+        let v = Vec3 {x: 1f64, y: 2f64, z: 3f64};
+        assert_eq!(m.mul_vec3(&v), v);
+    }
+
+    #[test]
+    fn mat3_inverse() {
+        let m = Mat3 {
+            data: [
+                2f64, 0f64, 0f64,
+                0f64, 2f64, 0f64,
+                0f64, 0f64, 2f64,
+            ]
+        };
+        let inv = m.inverse();
+        let v = Vec3 {x: 4f64, y: 6f64, z: 8f64};
+        let round_tripped = inv.mul_vec3(&m.mul_vec3(&v));
+        assert!((round_tripped.x - v.x).abs() < 1e-10);
+        assert!((round_tripped.y - v.y).abs() < 1e-10);
+        assert!((round_tripped.z - v.z).abs() < 1e-10);
+    }
+
+    #[test]
+    fn mat4_translation_transforms_point_not_direction() {
+        let t = Mat4::translation(&Vec3 {x: 1f64, y: 2f64, z: 3f64});
+        let v = Vec3 {x: 0f64, y: 0f64, z: 0f64};
+        assert_eq!(t.transform_point(&v), Vec3 {x: 1f64, y: 2f64, z: 3f64});
+        assert_eq!(t.transform_direction(&v), Vec3 {x: 0f64, y: 0f64, z: 0f64});
+    }
+
+    #[test]
+    fn mat4_mul_identity() {
+        let m = Mat4::translation(&Vec3 {x: 1f64, y: 2f64, z: 3f64});
+        let identity = Mat4::identity();
+        let result = &m * &identity;
+        assert_eq!(result, m);
+    }
+
+    #[test]
+    fn mat4_inverse_round_trip() {
+        let m = Mat4::translation(&Vec3 {x: 1f64, y: 2f64, z: 3f64});
+        let inv = m.inverse();
+        let v = Vec3 {x: 5f64, y: 6f64, z: 7f64};
+        let round_tripped = inv.transform_point(&m.transform_point(&v));
+        assert!((round_tripped.x - v.x).abs() < 1e-10);
+        assert!((round_tripped.y - v.y).abs() < 1e-10);
+        assert!((round_tripped.z - v.z).abs() < 1e-10);
+    }
+
+    #[test]
+    fn mat4_look_at_axes_are_orthonormal() {
+        let eye = Vec3 {x: 0f64, y: 0f64, z: 5f64};
+        let target = Vec3 {x: 0f64, y: 0f64, z: 0f64};
+        let up = Vec3 {x: 0f64, y: 1f64, z: 0f64};
+        let view = Mat4::look_at(&eye, &target, &up);
+
+        let eye_in_view = view.transform_point(&eye);
+        assert!(eye_in_view.x.abs() < 1e-10);
+        assert!(eye_in_view.y.abs() < 1e-10);
+        assert!(eye_in_view.z.abs() < 1e-10);
+    }
 }
 
 //===============================================
@@ -319,3 +1087,45 @@ mod ray {
         assert_eq!(point, expected_point);
     }
 }
+
+//===============================================
+// Sphere Unit Tests
+
+//===============================================
+#[cfg(test)]
+mod sphere {
+    use super::Ray;
+    use super::Sphere;
+    use super::Vec3;
+
+    #[test]
+    fn hit_nearest_root() {
+        let sphere = Sphere {
+            center: Vec3 {x: 0f64, y: 0f64, z: -1f64},
+            radius: 0.5f64,
+        };
+        let ray = Ray {
+            origin: Vec3 {x: 0f64, y: 0f64, z: 0f64},
+            dir: Vec3 {x: 0f64, y: 0f64, z: -1f64},
+        };
+
+        let hit = sphere.hit(&ray, 0f64, f64::MAX).unwrap();
+        assert_eq!(hit.t, 0.5f64);
+        assert_eq!(hit.point, Vec3 {x: 0f64, y: 0f64, z: -0.5f64});
+        assert_eq!(hit.normal, Vec3 {x: 0f64, y: 0f64, z: 1f64});
+    }
+
+    #[test]
+    fn miss() {
+        let sphere = Sphere {
+            center: Vec3 {x: 10f64, y: 0f64, z: 0f64},
+            radius: 0.5f64,
+        };
+        let ray = Ray {
+            origin: Vec3 {x: 0f64, y: 0f64, z: 0f64},
+            dir: Vec3 {x: 0f64, y: 0f64, z: -1f64},
+        };
+
+        assert_eq!(sphere.hit(&ray, 0f64, f64::MAX), None);
+    }
+}